@@ -6,4 +6,13 @@ pub trait BitRead {
     fn read_bit(&mut self) -> Result<u8, Error>;
     fn read_bit_as_bool(&mut self) -> Result<bool, Error>;
     fn read_bits_as_u8(&mut self, num_bits: usize) -> Result<u8, Error>;
+    /// Read |num_bits| (up to 16) starting at the current bit position, which may span multiple
+    /// bytes.
+    fn read_bits_as_u16(&mut self, num_bits: usize) -> Result<u16, Error>;
+    /// Read |num_bits| (up to 32) starting at the current bit position, which may span multiple
+    /// bytes.
+    fn read_bits_as_u32(&mut self, num_bits: usize) -> Result<u32, Error>;
+    /// Read |num_bits| (up to 64) starting at the current bit position, which may span multiple
+    /// bytes.
+    fn read_bits_as_u64(&mut self, num_bits: usize) -> Result<u64, Error>;
 }
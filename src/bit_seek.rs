@@ -0,0 +1,17 @@
+use crate::error::Error;
+
+/// Like |std::io::SeekFrom|, but positions are expressed as an absolute bit offset rather than a
+/// byte offset, so the cursor can be placed in the middle of a byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BitSeekFrom {
+    Start(u64),
+    Current(i64),
+    End(i64),
+}
+
+/// Analogous to |std::io::Seek|, but allows seeking to (and reporting) a bit-granular position
+/// instead of only byte boundaries.
+pub trait BitSeek {
+    fn seek_bits(&mut self, pos: BitSeekFrom) -> Result<u64, Error>;
+    fn bit_position(&self) -> u64;
+}
@@ -6,4 +6,13 @@ pub trait BitWrite {
     fn write_bit(&mut self, bit: u8) -> Result<(), Error>;
     fn write_u8_as_bits(&mut self, v: u8, num_bits: usize) -> Result<(), Error>;
     fn write_bool(&mut self, b: bool) -> Result<(), Error>;
+    /// Write the right-most |num_bits| (up to 16) of |v| starting at the current bit position,
+    /// which may span multiple bytes.
+    fn write_u16_as_bits(&mut self, v: u16, num_bits: usize) -> Result<(), Error>;
+    /// Write the right-most |num_bits| (up to 32) of |v| starting at the current bit position,
+    /// which may span multiple bytes.
+    fn write_u32_as_bits(&mut self, v: u32, num_bits: usize) -> Result<(), Error>;
+    /// Write the right-most |num_bits| (up to 64) of |v| starting at the current bit position,
+    /// which may span multiple bytes.
+    fn write_u64_as_bits(&mut self, v: u64, num_bits: usize) -> Result<(), Error>;
 }
@@ -7,4 +7,4 @@ use crate::{
 
 pub trait ByteBuffer: Read + BitRead + SizedByteBuffer + ByteBufferExts {}
 
-impl ByteBuffer for ByteBufferCursor {}
+impl<T: AsRef<[u8]>> ByteBuffer for ByteBufferCursor<T> {}
@@ -1,22 +1,29 @@
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use crate::bit_read::BitRead;
+use crate::bit_seek::{BitSeek, BitSeekFrom};
 use crate::bit_write::BitWrite;
 use crate::byte_buffer_exts::ByteBufferExts;
+use crate::byte_buffer_window::ByteBufferWindow;
 use crate::error::Error;
+use crate::growable_storage::GrowableStorage;
 use crate::helpers::get_u8_mask;
 use crate::sized_buffer::SizedByteBuffer;
 
 /// Similar to |std::io::Cursor| but designed to keep track of a buffer of bytes where amounts less
-/// than a single byte (i.e. some number of bits) can be read.
+/// than a single byte (i.e. some number of bits) can be read. Like |std::io::Cursor|, it's generic
+/// over the backing storage |T| so it can wrap an owned `Vec<u8>`, a borrowed `&[u8]`/`&mut [u8]`
+/// slice, an `Arc<[u8]>` for zero-copy sharing (note: `Arc<Vec<u8>>` won't work here, since it only
+/// implements `AsRef<Vec<u8>>`, not `AsRef<[u8]>` — use `Arc::from(vec)` to get an `Arc<[u8]>`
+/// instead), or anything else that behaves like a byte buffer.
 #[derive(Debug)]
-pub struct ByteBufferCursor {
-    byte_cursor: Cursor<Vec<u8>>,
+pub struct ByteBufferCursor<T> {
+    byte_cursor: Cursor<T>,
     bit_pos: u8,
 }
 
-impl ByteBufferCursor {
-    pub fn new(data: Vec<u8>) -> Self {
+impl<T> ByteBufferCursor<T> {
+    pub fn new(data: T) -> Self {
         ByteBufferCursor {
             byte_cursor: Cursor::new(data),
             bit_pos: 0,
@@ -32,46 +39,115 @@ impl ByteBufferCursor {
         }
     }
 
-    pub fn into_vec(self) -> Vec<u8> {
+    pub fn into_inner(self) -> T {
         self.byte_cursor.into_inner()
     }
 }
 
-impl Seek for ByteBufferCursor {
+impl<T: AsRef<[u8]>> Seek for ByteBufferCursor<T> {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64, std::io::Error> {
         self.bit_pos = 0;
         self.byte_cursor.seek(pos)
     }
 }
 
-impl SizedByteBuffer for ByteBufferCursor {
+impl<T: AsRef<[u8]>> BitSeek for ByteBufferCursor<T> {
+    fn bit_position(&self) -> u64 {
+        self.byte_cursor.position() * 8 + self.bit_pos as u64
+    }
+
+    fn seek_bits(&mut self, pos: BitSeekFrom) -> Result<u64, Error> {
+        let total_bits = self.byte_cursor.get_ref().as_ref().len() as u64 * 8;
+        let new_pos = match pos {
+            BitSeekFrom::Start(offset) => offset as i128,
+            BitSeekFrom::Current(offset) => self.bit_position() as i128 + offset as i128,
+            BitSeekFrom::End(offset) => total_bits as i128 + offset as i128,
+        };
+        if new_pos < 0 || new_pos as u64 > total_bits {
+            return Err(Error::InvalidCursorPosition(format!(
+                "cannot seek to bit offset {}, buffer only has {} bits",
+                new_pos, total_bits
+            )));
+        }
+        let new_pos = new_pos as u64;
+        self.byte_cursor.set_position(new_pos / 8);
+        self.bit_pos = (new_pos % 8) as u8;
+        Ok(new_pos)
+    }
+}
+
+impl<T: AsRef<[u8]>> SizedByteBuffer for ByteBufferCursor<T> {
     fn bytes_remaining(&self) -> usize {
         match self.bit_pos {
-            0 => self.byte_cursor.get_ref().len() - self.byte_cursor.position() as usize,
+            0 => {
+                self.byte_cursor.get_ref().as_ref().len() - self.byte_cursor.position() as usize
+            }
             // If we're in the middle of a byte, don't count that as a full byte remaining
             // (Note that this is a somewhat arbitrary decision, but it's what makes more sense
             // to me as of now)
-            _ => self.byte_cursor.get_ref().len() - self.byte_cursor.position() as usize - 1,
+            _ => {
+                self.byte_cursor.get_ref().as_ref().len()
+                    - self.byte_cursor.position() as usize
+                    - 1
+            }
         }
     }
 }
 
-impl ByteBufferCursor {
+impl<T: AsRef<[u8]>> ByteBufferCursor<T> {
     /// Return a copy of the byte at the byte cursor's current position. |bit_pos|
     /// refers to the current position within this byte.
     fn get_current_byte(&self) -> Result<u8, Error> {
-        Ok(self.byte_cursor.get_ref()[self.byte_cursor.position() as usize])
+        Ok(self.byte_cursor.get_ref().as_ref()[self.byte_cursor.position() as usize])
     }
 
-    /// Return a mutable reference to the byte at the byte cursor's current position. |bit_pos|
-    /// refers to the current position within this byte.
+    /// The total number of bits left to read or write, including any bits remaining in the byte
+    /// currently under the cursor. Returns 0 if the cursor has been seeked past the end of the
+    /// buffer (|std::io::Seek| doesn't bounds-check), rather than underflowing.
+    fn bits_remaining(&self) -> usize {
+        let len = self.byte_cursor.get_ref().as_ref().len();
+        let pos = self.byte_cursor.position() as usize;
+        if pos >= len {
+            return 0;
+        }
+        (len - pos) * 8 - self.bit_pos as usize
+    }
+
+    /// Return a window onto this cursor, starting at the current position and limited to |len|
+    /// bytes. See |ByteBufferWindow| for details.
+    pub fn sub_buffer(&mut self, len: usize) -> Result<ByteBufferWindow<'_, T>, Error> {
+        if len * 8 > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested a sub-buffer of {} bytes, but only {} bits remain in the buffer",
+                len,
+                self.bits_remaining()
+            )));
+        }
+        Ok(ByteBufferWindow::new(self, len))
+    }
+}
+
+impl<T: GrowableStorage> ByteBufferCursor<T> {
+    /// Return a mutable reference to the byte at the byte cursor's current position, growing the
+    /// backing storage first if the cursor is at or past its current end. |bit_pos| refers to the
+    /// current position within this byte. Fixed-size storage (e.g. a `Box<[u8]>` or `&mut [u8]`)
+    /// can't actually grow, so this still errors rather than indexing out of bounds in that case.
     fn get_current_byte_mut(&mut self) -> Result<&mut u8, Error> {
         let curr_pos = self.byte_cursor.position() as usize;
-        Ok(&mut self.byte_cursor.get_mut()[curr_pos])
+        self.byte_cursor.get_mut().ensure_len(curr_pos + 1);
+        let dest = self.byte_cursor.get_mut().as_mut();
+        if curr_pos >= dest.len() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "cannot write bit at position {}; backing storage is only {} bytes and cannot grow",
+                curr_pos,
+                dest.len()
+            )));
+        }
+        Ok(&mut dest[curr_pos])
     }
 }
 
-impl Read for ByteBufferCursor {
+impl<T: AsRef<[u8]>> Read for ByteBufferCursor<T> {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         match self.bit_pos {
             0 => self.byte_cursor.read(buf),
@@ -86,7 +162,7 @@ impl Read for ByteBufferCursor {
     }
 }
 
-impl BitRead for ByteBufferCursor {
+impl<T: AsRef<[u8]>> BitRead for ByteBufferCursor<T> {
     fn read_bit(&mut self) -> Result<u8, Error> {
         match self.bit_pos {
             8 => {
@@ -119,12 +195,105 @@ impl BitRead for ByteBufferCursor {
         self.increment_bit_pos(num_bits);
         Ok(result)
     }
+
+    fn read_bits_as_u16(&mut self, num_bits: usize) -> Result<u16, Error> {
+        if num_bits > 16 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits into a u16, which can hold at most 16",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut acc: u16 = 0;
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            let bits = self.read_bits_as_u8(take)?;
+            acc = (acc << take) | bits as u16;
+            num_bits_remaining -= take;
+        }
+        Ok(acc)
+    }
+
+    fn read_bits_as_u32(&mut self, num_bits: usize) -> Result<u32, Error> {
+        if num_bits > 32 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits into a u32, which can hold at most 32",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut acc: u32 = 0;
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            let bits = self.read_bits_as_u8(take)?;
+            acc = (acc << take) | bits as u32;
+            num_bits_remaining -= take;
+        }
+        Ok(acc)
+    }
+
+    fn read_bits_as_u64(&mut self, num_bits: usize) -> Result<u64, Error> {
+        if num_bits > 64 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits into a u64, which can hold at most 64",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut acc: u64 = 0;
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            let bits = self.read_bits_as_u8(take)?;
+            acc = (acc << take) | bits as u64;
+            num_bits_remaining -= take;
+        }
+        Ok(acc)
+    }
 }
 
-impl Write for ByteBufferCursor {
+impl<T: GrowableStorage> Write for ByteBufferCursor<T> {
     fn write(&mut self, buf: &[u8]) -> Result<usize, std::io::Error> {
         match self.bit_pos {
-            0 => self.byte_cursor.write(buf),
+            0 => {
+                let pos = self.byte_cursor.position() as usize;
+                self.byte_cursor.get_mut().ensure_len(pos + buf.len());
+                let dest = self.byte_cursor.get_mut().as_mut();
+                if pos > dest.len() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!(
+                            "cannot write at position {}; backing storage is only {} bytes and cannot grow",
+                            pos,
+                            dest.len()
+                        ),
+                    ));
+                }
+                let len = buf.len().min(dest.len() - pos);
+                dest[pos..pos + len].copy_from_slice(&buf[..len]);
+                self.byte_cursor.set_position((pos + len) as u64);
+                Ok(len)
+            }
             bp => Err(std::io::Error::new(
                 std::io::ErrorKind::Other,
                 format!(
@@ -140,7 +309,7 @@ impl Write for ByteBufferCursor {
     }
 }
 
-impl BitWrite for ByteBufferCursor {
+impl<T: GrowableStorage> BitWrite for ByteBufferCursor<T> {
     fn write_bit(&mut self, bit: u8) -> Result<(), Error> {
         let mask = !(0b10000000 >> self.bit_pos);
         let shift_amount = 7 - self.bit_pos;
@@ -198,11 +367,87 @@ impl BitWrite for ByteBufferCursor {
         self.increment_bit_pos(num_bits);
         Ok(())
     }
+
+    fn write_u16_as_bits(&mut self, v: u16, num_bits: usize) -> Result<(), Error> {
+        if num_bits > 16 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits from a u16, which can hold at most 16",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            // Pull off the next `take` bits, MSB-first, from whatever of `v` hasn't been written yet
+            let shift = num_bits_remaining - take;
+            let chunk = ((v >> shift) & ((1u16 << take) - 1)) as u8;
+            self.write_u8_as_bits(chunk, take)?;
+            num_bits_remaining -= take;
+        }
+        Ok(())
+    }
+
+    fn write_u32_as_bits(&mut self, v: u32, num_bits: usize) -> Result<(), Error> {
+        if num_bits > 32 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits from a u32, which can hold at most 32",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            let shift = num_bits_remaining - take;
+            let chunk = ((v >> shift) & ((1u32 << take) - 1)) as u8;
+            self.write_u8_as_bits(chunk, take)?;
+            num_bits_remaining -= take;
+        }
+        Ok(())
+    }
+
+    fn write_u64_as_bits(&mut self, v: u64, num_bits: usize) -> Result<(), Error> {
+        if num_bits > 64 {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits from a u64, which can hold at most 64",
+                num_bits
+            )));
+        }
+        if num_bits > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to write {} bits, but only {} bits remain in the buffer",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        let mut num_bits_remaining = num_bits;
+        while num_bits_remaining > 0 {
+            let take = num_bits_remaining.min(8 - self.bit_pos as usize);
+            let shift = num_bits_remaining - take;
+            let chunk = ((v >> shift) & ((1u64 << take) - 1)) as u8;
+            self.write_u8_as_bits(chunk, take)?;
+            num_bits_remaining -= take;
+        }
+        Ok(())
+    }
 }
 
-impl ByteBufferExts for ByteBufferCursor {
+impl<T: AsRef<[u8]>> ByteBufferExts for ByteBufferCursor<T> {
     fn peek_u8(&self) -> Result<u8, Error> {
-        Ok(self.byte_cursor.get_ref()[self.byte_cursor.position() as usize])
+        Ok(self.byte_cursor.get_ref().as_ref()[self.byte_cursor.position() as usize])
     }
 }
 
@@ -320,7 +565,7 @@ mod tests {
         cursor.write_bit(1).unwrap();
         cursor.write_bool(true).unwrap();
 
-        let data = cursor.into_vec();
+        let data = cursor.into_inner();
         assert_eq!(data[0], 0b11110000);
     }
 
@@ -331,7 +576,7 @@ mod tests {
 
         cursor.write_bit(0).unwrap();
 
-        assert_eq!(0b01111111, cursor.into_vec()[0]);
+        assert_eq!(0b01111111, cursor.into_inner()[0]);
     }
 
     #[test]
@@ -344,7 +589,7 @@ mod tests {
 
         cursor.write_u8_as_bits(2, 2).unwrap();
 
-        let data = cursor.into_vec();
+        let data = cursor.into_inner();
         assert_eq!(0b11110101, data[0]);
         assert_eq!(0b10000000, data[1]);
     }
@@ -358,7 +603,7 @@ mod tests {
 
         cursor.write_u8_as_bits(0b11111111, 3).unwrap();
 
-        assert_eq!(0b11100000, cursor.into_vec()[0]);
+        assert_eq!(0b11100000, cursor.into_inner()[0]);
     }
 
     #[test]
@@ -369,6 +614,141 @@ mod tests {
 
         cursor.write_u8_as_bits(0, 3).unwrap();
 
-        assert_eq!(0b00011111, cursor.into_vec()[0]);
+        assert_eq!(0b00011111, cursor.into_inner()[0]);
+    }
+
+    #[test]
+    fn test_read_bits_as_u16_crosses_byte_boundary() {
+        let data: Vec<u8> = vec![0b00000111, 0b11111000];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.read_bits_as_u8(5).unwrap();
+        assert_eq!(cursor.read_bits_as_u16(8).unwrap(), 0b1111_1111);
+    }
+
+    #[test]
+    fn test_read_bits_as_u32_crosses_multiple_bytes() {
+        let data: Vec<u8> = vec![0b00000000, 0b11111111, 0b11111111, 0b00000000];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.read_bits_as_u8(8).unwrap();
+        assert_eq!(cursor.read_bits_as_u32(16).unwrap(), 0b1111_1111_1111_1111);
+    }
+
+    #[test]
+    fn test_read_bits_as_u64_too_many_bits_is_error() {
+        let data: Vec<u8> = vec![0; 8];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert!(cursor.read_bits_as_u64(65).is_err());
+    }
+
+    #[test]
+    fn test_read_bits_as_u64_past_end_of_buffer_is_error() {
+        let data: Vec<u8> = vec![0; 2];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert!(cursor.read_bits_as_u64(32).is_err());
+    }
+
+    #[test]
+    fn test_read_bits_as_u16_after_seek_past_end_is_error() {
+        let data: Vec<u8> = vec![0; 3];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.seek(SeekFrom::Start(10)).unwrap();
+        assert!(cursor.read_bits_as_u16(4).is_err());
+    }
+
+    #[test]
+    fn test_write_u16_as_bits_crosses_byte_boundary() {
+        let data: Vec<u8> = vec![0, 0];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.write_u8_as_bits(0b111, 3).unwrap();
+        cursor.write_u16_as_bits(0b1_1111_1111, 9).unwrap();
+
+        let data = cursor.into_inner();
+        assert_eq!(0b11111111, data[0]);
+        assert_eq!(0b11110000, data[1]);
+    }
+
+    #[test]
+    fn test_write_u32_as_bits_past_end_of_buffer_is_error() {
+        let data: Vec<u8> = vec![0; 2];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert!(cursor.write_u32_as_bits(0xffff_ffff, 32).is_err());
+    }
+
+    #[test]
+    fn test_seek_bits_from_start() {
+        let data: Vec<u8> = vec![0b11110000, 0b00001111];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert_eq!(cursor.seek_bits(BitSeekFrom::Start(13)).unwrap(), 13);
+        assert_eq!(cursor.bit_position(), 13);
+        assert_eq!(cursor.read_bit().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_seek_bits_from_current() {
+        let data: Vec<u8> = vec![0, 0];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.seek_bits(BitSeekFrom::Start(4)).unwrap();
+        assert_eq!(cursor.seek_bits(BitSeekFrom::Current(3)).unwrap(), 7);
+        assert_eq!(cursor.seek_bits(BitSeekFrom::Current(-5)).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_seek_bits_from_end() {
+        let data: Vec<u8> = vec![0, 0];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert_eq!(cursor.seek_bits(BitSeekFrom::End(-1)).unwrap(), 15);
+    }
+
+    #[test]
+    fn test_seek_bits_past_end_is_error() {
+        let data: Vec<u8> = vec![0];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert!(cursor.seek_bits(BitSeekFrom::Start(9)).is_err());
+    }
+
+    #[test]
+    fn test_write_grows_empty_vec() {
+        let mut cursor = ByteBufferCursor::new(Vec::new());
+
+        cursor.write_all(&[1, 2, 3]).unwrap();
+
+        assert_eq!(cursor.into_inner(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_write_bit_grows_buffer_past_current_end() {
+        let mut cursor = ByteBufferCursor::new(Vec::new());
+
+        cursor.write_bit(1).unwrap();
+        cursor.write_u8_as_bits(0b1111, 4).unwrap();
+
+        assert_eq!(cursor.into_inner(), vec![0b11111000]);
+    }
+
+    #[test]
+    fn test_write_bit_past_end_of_fixed_size_storage_is_error() {
+        let mut cursor = ByteBufferCursor::new(Box::new([]) as Box<[u8]>);
+
+        assert!(cursor.write_bit(1).is_err());
+    }
+
+    #[test]
+    fn test_write_past_end_of_fixed_size_storage_after_seek_is_error() {
+        let data: Box<[u8]> = vec![1, 2, 3].into_boxed_slice();
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.seek(SeekFrom::Start(10)).unwrap();
+        assert!(cursor.write(&[9]).is_err());
     }
 }
@@ -1,7 +1,10 @@
 use std::io::Write;
 
-use crate::{bit_write::BitWrite, byte_buffer::ByteBuffer, byte_buffer_cursor::ByteBufferCursor};
+use crate::{
+    bit_write::BitWrite, byte_buffer::ByteBuffer, byte_buffer_cursor::ByteBufferCursor,
+    growable_storage::GrowableStorage,
+};
 
 pub trait ByteBufferMut: ByteBuffer + Write + BitWrite {}
 
-impl ByteBufferMut for ByteBufferCursor {}
+impl<T: AsRef<[u8]> + GrowableStorage> ByteBufferMut for ByteBufferCursor<T> {}
@@ -0,0 +1,237 @@
+use std::io::Read;
+
+use crate::bit_read::BitRead;
+use crate::bit_seek::{BitSeek, BitSeekFrom};
+use crate::byte_buffer_cursor::ByteBufferCursor;
+use crate::byte_buffer_exts::ByteBufferExts;
+use crate::error::Error;
+use crate::sized_buffer::SizedByteBuffer;
+
+/// A bounded view into a |ByteBufferCursor|, starting at the cursor's current position and
+/// limited to |len| bytes. Useful for parsing a length-prefixed sub-element (e.g. a TLV or
+/// extension block) without letting the sub-parser read past its declared boundary. Once the
+/// window is dropped (or |advance_parent| is called explicitly), the parent cursor's position is
+/// moved forward by |len| bytes, regardless of how much of the window was actually consumed.
+pub struct ByteBufferWindow<'a, T: AsRef<[u8]>> {
+    parent: &'a mut ByteBufferCursor<T>,
+    start_bit: u64,
+    end_bit: u64,
+    advanced: bool,
+}
+
+impl<'a, T: AsRef<[u8]>> ByteBufferWindow<'a, T> {
+    pub(crate) fn new(parent: &'a mut ByteBufferCursor<T>, len: usize) -> Self {
+        let start_bit = parent.bit_position();
+        let end_bit = start_bit + len as u64 * 8;
+        ByteBufferWindow {
+            parent,
+            start_bit,
+            end_bit,
+            advanced: false,
+        }
+    }
+
+    /// The number of bits left to read within this window.
+    fn bits_remaining(&self) -> u64 {
+        self.end_bit - self.parent.bit_position()
+    }
+
+    /// Move the parent cursor's position to the end of the window, even if the window wasn't
+    /// fully consumed. Called automatically on drop, but can be called explicitly to surface the
+    /// seek error instead of silently ignoring it.
+    pub fn advance_parent(mut self) -> Result<(), Error> {
+        self.advance_parent_impl()
+    }
+
+    fn advance_parent_impl(&mut self) -> Result<(), Error> {
+        if !self.advanced {
+            self.advanced = true;
+            self.parent.seek_bits(BitSeekFrom::Start(self.end_bit))?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> BitSeek for ByteBufferWindow<'a, T> {
+    fn bit_position(&self) -> u64 {
+        self.parent.bit_position()
+    }
+
+    fn seek_bits(&mut self, pos: BitSeekFrom) -> Result<u64, Error> {
+        let old_pos = self.parent.bit_position();
+        let new_pos = self.parent.seek_bits(pos)?;
+        if new_pos < self.start_bit || new_pos > self.end_bit {
+            self.parent.seek_bits(BitSeekFrom::Start(old_pos))?;
+            return Err(Error::InvalidCursorPosition(format!(
+                "cannot seek to bit {}, outside the buffer window [{}, {})",
+                new_pos, self.start_bit, self.end_bit
+            )));
+        }
+        Ok(new_pos)
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> SizedByteBuffer for ByteBufferWindow<'a, T> {
+    fn bytes_remaining(&self) -> usize {
+        (self.bits_remaining() / 8) as usize
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> ByteBufferExts for ByteBufferWindow<'a, T> {
+    fn peek_u8(&self) -> Result<u8, Error> {
+        self.parent.peek_u8()
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> Read for ByteBufferWindow<'a, T> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_remaining = self.bytes_remaining();
+        if bytes_remaining == 0 {
+            return Ok(0);
+        }
+        let len = buf.len().min(bytes_remaining);
+        self.parent.read(&mut buf[..len])
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> BitRead for ByteBufferWindow<'a, T> {
+    fn read_bit(&mut self) -> Result<u8, Error> {
+        if self.bits_remaining() == 0 {
+            return Err(Error::InvalidCursorPosition(
+                "attempted to read past the end of the buffer window".to_string(),
+            ));
+        }
+        self.parent.read_bit()
+    }
+
+    fn read_bit_as_bool(&mut self) -> Result<bool, Error> {
+        Ok(self.read_bit()? > 0)
+    }
+
+    fn read_bits_as_u8(&mut self, num_bits: usize) -> Result<u8, Error> {
+        if num_bits as u64 > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} remain in the buffer window",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        self.parent.read_bits_as_u8(num_bits)
+    }
+
+    fn read_bits_as_u16(&mut self, num_bits: usize) -> Result<u16, Error> {
+        if num_bits as u64 > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} remain in the buffer window",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        self.parent.read_bits_as_u16(num_bits)
+    }
+
+    fn read_bits_as_u32(&mut self, num_bits: usize) -> Result<u32, Error> {
+        if num_bits as u64 > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} remain in the buffer window",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        self.parent.read_bits_as_u32(num_bits)
+    }
+
+    fn read_bits_as_u64(&mut self, num_bits: usize) -> Result<u64, Error> {
+        if num_bits as u64 > self.bits_remaining() {
+            return Err(Error::InvalidCursorPosition(format!(
+                "requested to read {} bits, but only {} remain in the buffer window",
+                num_bits,
+                self.bits_remaining()
+            )));
+        }
+        self.parent.read_bits_as_u64(num_bits)
+    }
+}
+
+impl<'a, T: AsRef<[u8]>> Drop for ByteBufferWindow<'a, T> {
+    fn drop(&mut self) {
+        // Dropping is infallible, so there's nowhere to surface a seek error; the only way it can
+        // fail is if the window itself was constructed past the end of the buffer, which
+        // `sub_buffer` already guards against.
+        let _ = self.advance_parent_impl();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_window_clamps_reads_to_its_length() {
+        let data: Vec<u8> = vec![1, 2, 3, 4, 5];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        let mut window = cursor.sub_buffer(2).unwrap();
+        assert_eq!(window.bytes_remaining(), 2);
+
+        let mut buf = [0; 1];
+        assert!(window.read(&mut buf).is_ok());
+        assert_eq!(buf[0], 1);
+        assert!(window.read(&mut buf).is_ok());
+        assert_eq!(buf[0], 2);
+
+        // The window is exhausted, even though the parent buffer has more data
+        assert_eq!(window.bytes_remaining(), 0);
+        assert_eq!(window.read(&mut buf).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_window_advances_parent_on_drop() {
+        let data: Vec<u8> = vec![1, 2, 3, 4, 5];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        {
+            let mut window = cursor.sub_buffer(2).unwrap();
+            // Only consume part of the window
+            let mut buf = [0; 1];
+            window.read_exact(&mut buf).unwrap();
+        }
+
+        // The parent should have advanced by the full window length, not just what was consumed
+        let mut buf = [0; 1];
+        cursor.read_exact(&mut buf).unwrap();
+        assert_eq!(buf[0], 3);
+    }
+
+    #[test]
+    fn test_window_bit_reads_error_past_its_end() {
+        let data: Vec<u8> = vec![0b11111111, 0];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        let mut window = cursor.sub_buffer(1).unwrap();
+        for _ in 0..8 {
+            window.read_bit().unwrap();
+        }
+        assert!(window.read_bit().is_err());
+    }
+
+    #[test]
+    fn test_window_supports_byte_order_reads_after_realigning() {
+        use crate::byte_order_read::ByteOrderRead;
+
+        let data: Vec<u8> = vec![0b11110000, 0x00, 0x01, 0xff];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        let mut window = cursor.sub_buffer(3).unwrap();
+        window.read_bits_as_u8(8).unwrap();
+        assert_eq!(window.read_u16_be().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_sub_buffer_past_end_of_parent_is_error() {
+        let data: Vec<u8> = vec![1, 2];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        assert!(cursor.sub_buffer(3).is_err());
+    }
+}
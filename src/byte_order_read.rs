@@ -0,0 +1,113 @@
+use std::io::Read;
+
+use crate::bit_seek::BitSeek;
+use crate::error::Error;
+
+/// Endianness-aware helpers for reading multi-byte integers at the current byte position. All
+/// methods require the cursor to be byte-aligned (consistent with how |std::io::Read| already
+/// rejects a mid-byte byte-level read), so a parser can read a few flag bits, realign, then pull
+/// out a length or similar field.
+pub trait ByteOrderRead {
+    fn read_u16_be(&mut self) -> Result<u16, Error>;
+    fn read_u16_le(&mut self) -> Result<u16, Error>;
+    fn read_u32_be(&mut self) -> Result<u32, Error>;
+    fn read_u32_le(&mut self) -> Result<u32, Error>;
+    fn read_u64_be(&mut self) -> Result<u64, Error>;
+    fn read_u64_le(&mut self) -> Result<u64, Error>;
+}
+
+fn check_byte_aligned<R: BitSeek>(reader: &R) -> Result<(), Error> {
+    if !reader.bit_position().is_multiple_of(8) {
+        return Err(Error::InvalidCursorPosition(format!(
+            "cannot do a byte-level read; cursor is currently on bit {}",
+            reader.bit_position() % 8
+        )));
+    }
+    Ok(())
+}
+
+impl<R: Read + BitSeek> ByteOrderRead for R {
+    fn read_u16_be(&mut self) -> Result<u16, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u16::from_be_bytes(buf))
+    }
+
+    fn read_u16_le(&mut self) -> Result<u16, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 2];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u16::from_le_bytes(buf))
+    }
+
+    fn read_u32_be(&mut self) -> Result<u32, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    fn read_u32_le(&mut self) -> Result<u32, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 4];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    fn read_u64_be(&mut self) -> Result<u64, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u64::from_be_bytes(buf))
+    }
+
+    fn read_u64_le(&mut self) -> Result<u64, Error> {
+        check_byte_aligned(self)?;
+        let mut buf = [0u8; 8];
+        self.read_exact(&mut buf)
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))?;
+        Ok(u64::from_le_bytes(buf))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_read::BitRead;
+    use crate::byte_buffer_cursor::ByteBufferCursor;
+
+    #[test]
+    fn test_read_u16_be_and_le() {
+        let data: Vec<u8> = vec![0x01, 0x02];
+        let mut cursor = ByteBufferCursor::new(data);
+        assert_eq!(cursor.read_u16_be().unwrap(), 0x0102);
+
+        let data: Vec<u8> = vec![0x01, 0x02];
+        let mut cursor = ByteBufferCursor::new(data);
+        assert_eq!(cursor.read_u16_le().unwrap(), 0x0201);
+    }
+
+    #[test]
+    fn test_read_u32_be_after_realigning_from_bits() {
+        let data: Vec<u8> = vec![0b11110000, 0x00, 0x00, 0x00, 0x01];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.read_bits_as_u8(8).unwrap();
+        assert_eq!(cursor.read_u32_be().unwrap(), 1);
+    }
+
+    #[test]
+    fn test_read_u64_be_while_mid_byte_is_error() {
+        let data: Vec<u8> = vec![0; 9];
+        let mut cursor = ByteBufferCursor::new(data);
+
+        cursor.read_bits_as_u8(4).unwrap();
+        assert!(cursor.read_u64_be().is_err());
+    }
+}
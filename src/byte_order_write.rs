@@ -0,0 +1,101 @@
+use std::io::Write;
+
+use crate::bit_seek::BitSeek;
+use crate::error::Error;
+
+/// Endianness-aware helpers for writing multi-byte integers at the current byte position. All
+/// methods require the cursor to be byte-aligned (consistent with how |std::io::Write| already
+/// rejects a mid-byte byte-level write), so a parser can write a few flag bits, realign, then
+/// write out a length or similar field.
+pub trait ByteOrderWrite {
+    fn write_u16_be(&mut self, v: u16) -> Result<(), Error>;
+    fn write_u16_le(&mut self, v: u16) -> Result<(), Error>;
+    fn write_u32_be(&mut self, v: u32) -> Result<(), Error>;
+    fn write_u32_le(&mut self, v: u32) -> Result<(), Error>;
+    fn write_u64_be(&mut self, v: u64) -> Result<(), Error>;
+    fn write_u64_le(&mut self, v: u64) -> Result<(), Error>;
+}
+
+fn check_byte_aligned<W: BitSeek>(writer: &W) -> Result<(), Error> {
+    if !writer.bit_position().is_multiple_of(8) {
+        return Err(Error::InvalidCursorPosition(format!(
+            "cannot do a byte-level write; cursor is currently on bit {}",
+            writer.bit_position() % 8
+        )));
+    }
+    Ok(())
+}
+
+impl<W: Write + BitSeek> ByteOrderWrite for W {
+    fn write_u16_be(&mut self, v: u16) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_be_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+
+    fn write_u16_le(&mut self, v: u16) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_le_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+
+    fn write_u32_be(&mut self, v: u32) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_be_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+
+    fn write_u32_le(&mut self, v: u32) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_le_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+
+    fn write_u64_be(&mut self, v: u64) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_be_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+
+    fn write_u64_le(&mut self, v: u64) -> Result<(), Error> {
+        check_byte_aligned(self)?;
+        self.write_all(&v.to_le_bytes())
+            .map_err(|e| Error::InvalidCursorPosition(e.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bit_write::BitWrite;
+    use crate::byte_buffer_cursor::ByteBufferCursor;
+
+    #[test]
+    fn test_write_u16_be_and_le() {
+        let mut cursor = ByteBufferCursor::new(Vec::new());
+        cursor.write_u16_be(0x0102).unwrap();
+        assert_eq!(cursor.into_inner(), vec![0x01, 0x02]);
+
+        let mut cursor = ByteBufferCursor::new(Vec::new());
+        cursor.write_u16_le(0x0102).unwrap();
+        assert_eq!(cursor.into_inner(), vec![0x02, 0x01]);
+    }
+
+    #[test]
+    fn test_write_u32_be_after_realigning_from_bits() {
+        let mut cursor = ByteBufferCursor::new(Vec::new());
+
+        cursor.write_u8_as_bits(0xff, 8).unwrap();
+        cursor.write_u32_be(1).unwrap();
+
+        assert_eq!(cursor.into_inner(), vec![0xff, 0x00, 0x00, 0x00, 0x01]);
+    }
+
+    #[test]
+    fn test_write_u64_be_while_mid_byte_is_error() {
+        let mut cursor = ByteBufferCursor::new(vec![0; 9]);
+
+        cursor.write_u8_as_bits(0, 4).unwrap();
+        assert!(cursor.write_u64_be(0).is_err());
+    }
+}
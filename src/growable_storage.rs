@@ -0,0 +1,23 @@
+/// Backing storage that can grow to make room for a write past its current length, mirroring how
+/// |std::io::Cursor<Vec<u8>>| extends the underlying vec on write. Fixed-size storage (e.g. a
+/// borrowed `&mut [u8]`) simply can't grow, so writing past its end is still rejected.
+pub trait GrowableStorage: AsRef<[u8]> + AsMut<[u8]> {
+    /// Ensure the storage is at least |len| bytes long, filling any newly added bytes with zero.
+    fn ensure_len(&mut self, len: usize);
+}
+
+impl GrowableStorage for Vec<u8> {
+    fn ensure_len(&mut self, len: usize) {
+        if self.len() < len {
+            self.resize(len, 0);
+        }
+    }
+}
+
+impl GrowableStorage for Box<[u8]> {
+    fn ensure_len(&mut self, _len: usize) {}
+}
+
+impl GrowableStorage for &mut [u8] {
+    fn ensure_len(&mut self, _len: usize) {}
+}